@@ -1,5 +1,5 @@
-use std::mem::size_of;
-use std::ops::Shl;
+use core::mem::size_of;
+use core::ops::Shl;
 
 pub trait NearestMultiple<T> {
     fn round_up_to_multiple(&self, multiple: T) -> T;
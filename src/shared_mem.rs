@@ -1,7 +1,7 @@
-use std::mem::size_of;
+use core::mem::size_of;
+use core::ffi::c_void;
 use crate::rounding::{NearestMultiple};
 use buffer_sys::{doublemap, doublemunlock, pagesize};
-use std::os::raw::c_void;
 
 #[derive(Debug)]
 pub enum Error {
@@ -33,6 +33,7 @@ impl<T> SharedMemory<T> {
 
 impl<T> Drop for SharedMemory<T> {
     fn drop(&mut self) {
+        #[cfg(feature = "std")]
         println!("kaboom {:p}", self.ptr);
         let size = self.len * size_of::<T>();
         // Will actually free 2*size that was mapped by doublemap
@@ -1,210 +1,747 @@
-use std::slice;
-use std::sync::{Arc, Mutex, Condvar};
-use std::mem::size_of;
+// `std` is on by default and brings OS-backed synchronization: the
+// condvar-based blocking strategy, plus the multi-reader registry and
+// live-resizing support, which both need a `Mutex`/`RwLock` that `core`
+// and `alloc` alone don't provide. Without it, this still builds as a
+// `no_std` crate offering the basic single-reader/single-writer ring,
+// with `read_exact` falling back to a spin loop. The crate root is
+// expected to carry:
+//     #![cfg_attr(not(feature = "std"), no_std)]
+//     #[cfg(not(feature = "std"))]
+//     extern crate alloc;
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+
+use core::slice;
+use core::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(feature = "std")]
+use core::sync::atomic::AtomicBool;
+use core::mem::size_of;
+#[cfg(feature = "std")]
+use core::ptr;
 use crate::shared_mem::SharedMemory;
+
+#[cfg(feature = "std")]
+use std::sync::{Arc, Mutex, RwLock, Condvar};
+#[cfg(feature = "std")]
 use std::io;
 
+#[cfg(not(feature = "std"))]
+use alloc::sync::Arc;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+// Blocking strategy used by `read_exact` to wait for data. `CondvarBlocking`
+// (std) and `SpinBlocking` (no_std) are the two built in, but `Writer`/
+// `Reader` are generic over this trait, so a caller with different needs
+// (e.g. an async-waker-based or hybrid spin/park strategy) can supply their
+// own via `new_with_blocking`/`new_multi_with_blocking`.
+pub trait Blocking: core::fmt::Debug {
+    // Block until `predicate` returns false, re-checking it on every
+    // wakeup. Implementations must hold whatever synchronization they use
+    // across the *first* check too, so a notification that lands between
+    // the caller's initial check and this call is never lost.
+    fn wait_while(&self, predicate: &mut dyn FnMut() -> bool);
+    fn notify(&self);
+}
+
+#[cfg(feature = "std")]
+#[derive(Debug, Default)]
+pub struct CondvarBlocking {
+    lock: Mutex<()>,
+    cond: Condvar,
+    // Count of waiters currently parked (or about to park) in `wait_while`,
+    // so `notify` can skip the lock/syscall entirely on the common case of
+    // nobody waiting, which is every `produce`/`consume` call on a buffer
+    // nobody is blocked on.
+    waiting: AtomicUsize,
+}
+
+#[cfg(feature = "std")]
+impl Blocking for CondvarBlocking {
+    fn wait_while(&self, predicate: &mut dyn FnMut() -> bool) {
+        let mut guard = self.lock.lock().unwrap();
+        // Increment before the first predicate check, still under the
+        // lock: any `produce`/`consume` that already happened is visible
+        // to this check (their stores happen-before this load), and any
+        // that happens afterwards will see `waiting > 0` and take the
+        // lock below, so no wakeup in between can be missed.
+        self.waiting.fetch_add(1, Ordering::SeqCst);
+        while predicate() {
+            guard = self.cond.wait(guard).unwrap();
+        }
+        self.waiting.fetch_sub(1, Ordering::SeqCst);
+    }
+
+    fn notify(&self) {
+        if self.waiting.load(Ordering::SeqCst) == 0 {
+            return;
+        }
+        // Taking the lock here (instead of just notifying) is what makes
+        // `wait_while` race-free: it forces us to wait until the waiter
+        // either hasn't started its check yet, or is already inside
+        // `cond.wait()`, so it can never miss this wakeup.
+        drop(self.lock.lock().unwrap());
+        self.cond.notify_all();
+    }
+}
+
+#[cfg(not(feature = "std"))]
+#[derive(Debug, Default)]
+pub struct SpinBlocking;
+
+#[cfg(not(feature = "std"))]
+impl Blocking for SpinBlocking {
+    fn wait_while(&self, predicate: &mut dyn FnMut() -> bool) {
+        while predicate() {
+            core::hint::spin_loop();
+        }
+    }
+
+    fn notify(&self) {
+        // Nothing to do: waiters are spinning on the atomic state directly.
+    }
+}
+
+#[cfg(feature = "std")]
+type DefaultBlocking = CondvarBlocking;
+#[cfg(not(feature = "std"))]
+type DefaultBlocking = SpinBlocking;
+
+#[derive(Debug)]
+pub enum ResizeError {
+    // the new target capacity is smaller than the data currently buffered
+    WouldDiscardData,
+    Allocate(crate::shared_mem::Error),
+}
+
+// Snapshot of how full a buffer is, from one handle's point of view, plus
+// the capacity it's actually backed by and the capacity it's aiming for
+// (see `Writer::set_target_capacity`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BufferLimits {
+    pub len: usize,
+    pub capacity: usize,
+    pub target_capacity: usize,
+}
+
 #[derive(Debug)]
 pub struct Position {
-    write: usize,
-    read: usize,
-    capacity: usize,
+    // Monotonically increasing write cursor; wraps modulo `capacity` via masking.
+    write: AtomicUsize,
+    // The first reader's cursor is always here, lock-free, so the common
+    // single-reader case (including plain `new()`, which is just
+    // `new_multi(cap, 1)`) never touches a mutex on the `produce`/`write`
+    // hot path. Extra readers beyond the first, if any, live in `extra_reads`
+    // behind a `Mutex` since the registry can grow at runtime via
+    // `add_reader`; `has_extra_reads` lets `min_read`/`write_len` skip that
+    // lock entirely when there are none. `no_std` has no `Mutex` to guard a
+    // dynamically growing registry, so it only ever supports the one reader
+    // created alongside the writer.
+    #[cfg(feature = "std")]
+    read0: Arc<AtomicUsize>,
+    #[cfg(feature = "std")]
+    extra_reads: Mutex<Vec<Arc<AtomicUsize>>>,
+    #[cfg(feature = "std")]
+    has_extra_reads: AtomicBool,
+    #[cfg(not(feature = "std"))]
+    read: Arc<AtomicUsize>,
+    // `no_std` has no live-resizing support, so its capacity never changes
+    // after construction and can live here directly. Under `std` the
+    // current capacity (and the origin it's paired with) instead live in
+    // `Generation`, bundled with the backing pointer they apply to - see
+    // the comment on `ShmCell`.
+    #[cfg(not(feature = "std"))]
+    capacity: AtomicUsize,
+    target_capacity: AtomicUsize,
 }
 
 impl Position {
-    fn new(capacity: usize) -> Position {
+    #[cfg(feature = "std")]
+    fn new(capacity: usize, n_readers: usize) -> Position {
         // Has to be power of two for wrapping arithmetic.
         assert_eq!(capacity.is_power_of_two(), true);
-        Position{write: 0, read: 0, capacity}
+        assert!(n_readers >= 1);
+        let read0 = Arc::new(AtomicUsize::new(0));
+        let extra_reads: Vec<_> = (1..n_readers).map(|_| Arc::new(AtomicUsize::new(0))).collect();
+        let has_extra_reads = !extra_reads.is_empty();
+        Position{
+            write: AtomicUsize::new(0),
+            read0,
+            extra_reads: Mutex::new(extra_reads),
+            has_extra_reads: AtomicBool::new(has_extra_reads),
+            target_capacity: AtomicUsize::new(capacity),
+        }
     }
 
-    // items available for writing
-    fn write_len(&self) -> usize {
-        self.capacity.wrapping_sub(self.write.wrapping_sub(self.read))
+    #[cfg(not(feature = "std"))]
+    fn new(capacity: usize, n_readers: usize) -> Position {
+        // Has to be power of two for wrapping arithmetic.
+        assert_eq!(capacity.is_power_of_two(), true);
+        assert_eq!(n_readers, 1, "no_std builds only support a single reader");
+        Position{
+            write: AtomicUsize::new(0),
+            read: Arc::new(AtomicUsize::new(0)),
+            capacity: AtomicUsize::new(capacity),
+            target_capacity: AtomicUsize::new(capacity),
+        }
     }
 
-    // items available for reading
-    fn read_len(&self) -> usize {
-        self.write.wrapping_sub(self.read)
+    #[cfg(not(feature = "std"))]
+    fn capacity(&self) -> usize {
+        self.capacity.load(Ordering::Relaxed)
     }
 
-    // item write offset into memory
-    fn write_offset(&self) -> usize {
-        self.write & (self.capacity - 1)
+    fn target_capacity(&self) -> usize {
+        self.target_capacity.load(Ordering::Relaxed)
     }
 
-    // item read offset into memory
-    fn read_offset(&self) -> usize {
-        self.read & (self.capacity - 1)
+    #[cfg(feature = "std")]
+    fn reader_cursors(&self) -> Vec<Arc<AtomicUsize>> {
+        let mut cursors = vec![self.read0.clone()];
+        if self.has_extra_reads.load(Ordering::Acquire) {
+            cursors.extend(self.extra_reads.lock().unwrap().iter().cloned());
+        }
+        cursors
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn reader_cursors(&self) -> Vec<Arc<AtomicUsize>> {
+        [self.read.clone()].into_iter().collect()
+    }
+
+    // register a new reader, starting from the current write position so it
+    // only sees samples produced from this point on
+    #[cfg(feature = "std")]
+    fn add_reader(&self) -> Arc<AtomicUsize> {
+        let cursor = Arc::new(AtomicUsize::new(self.write.load(Ordering::Acquire)));
+        self.extra_reads.lock().unwrap().push(cursor.clone());
+        self.has_extra_reads.store(true, Ordering::Release);
+        cursor
+    }
+
+    // read cursor of the slowest reader, i.e. the oldest still-needed sample.
+    // Skips the `extra_reads` lock entirely in the single-reader case (the
+    // common one: plain `new()` never has extra readers), so this stays
+    // wait-free on the hot `write_len`/`produce` path unless there is more
+    // than one reader (from `new_multi` with `n_readers > 1`, or from
+    // `add_reader`).
+    #[cfg(feature = "std")]
+    fn min_read(&self) -> usize {
+        let read0 = self.read0.load(Ordering::Acquire);
+        if !self.has_extra_reads.load(Ordering::Acquire) {
+            return read0;
+        }
+        self.extra_reads.lock().unwrap().iter()
+            .map(|r| r.load(Ordering::Acquire))
+            .fold(read0, usize::min)
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn min_read(&self) -> usize {
+        self.read.load(Ordering::Acquire)
+    }
+
+    // items available for writing, against a given capacity. `capacity`
+    // is a parameter rather than `self.capacity()` so callers can pass one
+    // read atomically together with the backing pointer it applies to -
+    // see `shm_snapshot`.
+    fn write_len(&self, capacity: usize) -> usize {
+        let write = self.write.load(Ordering::Relaxed);
+        let read = self.min_read();
+        capacity.wrapping_sub(write.wrapping_sub(read))
+    }
+
+    // item write offset into memory
+    fn write_offset(&self, capacity: usize, origin: usize) -> usize {
+        let write = self.write.load(Ordering::Relaxed);
+        write.wrapping_sub(origin) & (capacity - 1)
     }
 
     // shortcut to get both
-    fn write_offset_len(&self) -> (usize, usize) {
-        (self.write_offset() , self.write_len())
+    fn write_offset_len(&self, capacity: usize, origin: usize) -> (usize, usize) {
+        (self.write_offset(capacity, origin), self.write_len(capacity))
+    }
+
+    fn produce(&self, amount: usize, capacity: usize) {
+        // move write pointer forward and publish it to the readers
+        assert!(amount <= self.write_len(capacity));
+        let write = self.write.load(Ordering::Relaxed);
+        self.write.store(write.wrapping_add(amount), Ordering::Release);
+    }
+
+    // items available for a given reader
+    fn read_len(&self, read: &AtomicUsize) -> usize {
+        let read = read.load(Ordering::Relaxed);
+        let write = self.write.load(Ordering::Acquire);
+        write.wrapping_sub(read)
+    }
+
+    // read offset into memory for a given reader
+    fn read_offset(&self, read: &AtomicUsize, capacity: usize, origin: usize) -> usize {
+        read.load(Ordering::Relaxed).wrapping_sub(origin) & (capacity - 1)
     }
 
     // shortcut to get both
-    fn read_offset_len(&self) -> (usize, usize) {
-        (self.read_offset(), self.read_len())
+    fn read_offset_len(&self, read: &AtomicUsize, capacity: usize, origin: usize) -> (usize, usize) {
+        (self.read_offset(read, capacity, origin), self.read_len(read))
     }
 
-    fn produce(&mut self, amount: usize) {
-        // move write pointer forward
-        assert!(amount <= self.write_len());
-        self.write = self.write.wrapping_add(amount);
+    fn consume(&self, read: &AtomicUsize, amount: usize) {
+        // move this reader's cursor forward and publish it to the writer
+        assert!(amount <= self.read_len(read));
+        let pos = read.load(Ordering::Relaxed);
+        read.store(pos.wrapping_add(amount), Ordering::Release);
+    }
+
+    // absolute index of the next sample that will be produced
+    fn produced(&self) -> usize {
+        self.write.load(Ordering::Acquire)
     }
 
-    fn consume(&mut self, amount: usize) {
-        // move read pointer forward
-        assert!(amount <= self.read_len());
-        self.read = self.read.wrapping_add(amount);
+    // oldest absolute index still resident in the buffer, against a given
+    // (capacity, origin) pair - see `write_len` on why those are passed in.
+    fn oldest_retained(&self, capacity: usize, origin: usize) -> usize {
+        // Before a capacity's worth has ever been produced, nothing has
+        // been overwritten yet, so this must not wrap below 0. And no
+        // index older than `origin` was ever copied into the current
+        // backing allocation, regardless of what `capacity` would suggest.
+        self.produced().saturating_sub(capacity).max(origin)
     }
 }
 
+// One backing allocation together with the capacity and origin (the
+// absolute index mapped to its physical offset 0) it was sized/rebased
+// for - see `ShmCell`.
+#[cfg(feature = "std")]
 #[derive(Debug)]
-pub struct Writer<T> {
+struct Generation<T> {
     shm: Arc<SharedMemory<T>>,
-    pos: Arc<Mutex<Position>>,
-    cond: Arc<Condvar>
+    capacity: usize,
+    origin: usize,
+}
+
+// The writer's view of the buffer's backing storage. Under `std` it sits
+// behind an `RwLock` so `Writer::set_target_capacity` can swap it out for
+// a differently sized allocation without tearing down the ring; `no_std`
+// has no live-resizing support, so it's just a plain `Arc`.
+//
+// `current` holds the pointer bundled together with the capacity/origin it
+// applies to (a `Generation`), rather than as three independently-read
+// values: a reader that computed an offset from one generation's
+// capacity/origin and then fetched the pointer from a different,
+// concurrently swapped-in generation would index the new allocation with
+// an offset that only made sense for the old one. Reading all three out of
+// a single cloned `Arc` in one lock acquisition (`shm_snapshot`) is what
+// rules that out.
+//
+// `as_slice`/`as_mut_slice`/`get_from` return references tied only to
+// `&self`, not to any lock guard, so a `SharedMemory` that a resize swaps
+// out must not actually be unmapped while a slice borrowed from it could
+// still be alive: `retired` keeps every replaced allocation around for the
+// remaining lifetime of the buffer instead of dropping it immediately, so
+// `SharedMemory::drop`'s `doublemunlock` never runs out from under a live
+// reference.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+struct ShmCell<T> {
+    current: RwLock<Arc<Generation<T>>>,
+    retired: Mutex<Vec<Arc<SharedMemory<T>>>>,
+}
+#[cfg(feature = "std")]
+type ShmHandle<T> = Arc<ShmCell<T>>;
+#[cfg(not(feature = "std"))]
+type ShmHandle<T> = Arc<SharedMemory<T>>;
+
+// One consistent (pointer, capacity, origin) reading, safe to use for both
+// an offset computation and the pointer it indexes into. Under `std` this
+// is a single `RwLock` read plus an `Arc` clone, so a concurrent
+// `set_target_capacity` can never be observed half-applied; under `no_std`
+// there's no resize at all, so capacity is fixed at construction and
+// origin is always 0.
+#[cfg(feature = "std")]
+fn shm_snapshot<T>(shm: &ShmHandle<T>, _pos: &Position) -> (*mut T, usize, usize) {
+    let generation = shm.current.read().unwrap().clone();
+    (generation.shm.ptr, generation.capacity, generation.origin)
+}
+#[cfg(not(feature = "std"))]
+fn shm_snapshot<T>(shm: &ShmHandle<T>, pos: &Position) -> (*mut T, usize, usize) {
+    (shm.ptr, pos.capacity(), 0)
+}
+
+#[derive(Debug)]
+pub struct Writer<T, B = DefaultBlocking> {
+    shm: ShmHandle<T>,
+    pos: Arc<Position>,
+    blocking: Arc<B>,
 }
 
-impl<T: Copy> Writer<T> {
+impl<T: Copy, B: Blocking> Writer<T, B> {
     pub fn as_mut_slice(&mut self) -> &mut [T] {
-        let mut pos = self.pos.lock().unwrap();
-        let (write_offset, write_len) = pos.write_offset_len();
-        //let write_offset = pos.write_offset();
+        let (ptr, capacity, origin) = shm_snapshot(&self.shm, &self.pos);
+        let (write_offset, write_len) = self.pos.write_offset_len(capacity, origin);
         // len is number of elements
         unsafe {
-            slice::from_raw_parts_mut(
-                self.shm.ptr.offset(write_offset as isize),
-                write_len)
+            slice::from_raw_parts_mut(ptr.offset(write_offset as isize), write_len)
         }
     }
 
     pub fn produce(&mut self, amount: usize) {
-        let mut pos = self.pos.lock().unwrap();
+        let (_, capacity, _) = shm_snapshot(&self.shm, &self.pos);
         // move write pointer forward
-        pos.produce(amount);
-        self.cond.notify_one();
+        self.pos.produce(amount, capacity);
+        // Wake readers that might be parked in read_exact.
+        self.blocking.notify();
     }
 
-    pub fn write(&mut self, buf: &[T]) -> Result<usize, io::Error> {
+    pub fn write(&mut self, buf: &[T]) -> usize {
+        let (ptr, capacity, origin) = shm_snapshot(&self.shm, &self.pos);
         let copy_len = {
-            let mut pos = self.pos.lock().unwrap();
-            let (write_offset, write_len) = pos.write_offset_len();
+            let (write_offset, write_len) = self.pos.write_offset_len(capacity, origin);
             let copy_len = write_len.min(buf.len());
             // len is number of item
             let dest = unsafe {
-                slice::from_raw_parts_mut(
-                    self.shm.ptr.offset(write_offset as isize),
-                    write_len)
+                slice::from_raw_parts_mut(ptr.offset(write_offset as isize), write_len)
             };
             dest[0..copy_len].copy_from_slice(&buf[0..copy_len]);
             copy_len
         };
-        self.produce(copy_len);
-        Ok(copy_len)
+        self.pos.produce(copy_len, capacity);
+        self.blocking.notify();
+        copy_len
+    }
+
+    // Absolute index of the next sample that will be produced.
+    pub fn produced_index(&self) -> usize {
+        self.pos.produced()
+    }
+
+    // Mint a new reader that starts consuming from the current write
+    // position, independent of any existing readers.
+    #[cfg(feature = "std")]
+    pub fn add_reader(&self) -> Reader<T, B> {
+        Reader {
+            shm: self.shm.clone(),
+            pos: self.pos.clone(),
+            read: self.pos.add_reader(),
+            blocking: self.blocking.clone(),
+        }
+    }
+
+    pub fn limits(&self) -> BufferLimits {
+        let (_, capacity, _) = shm_snapshot(&self.shm, &self.pos);
+        BufferLimits {
+            len: capacity - self.pos.write_len(capacity),
+            capacity,
+            target_capacity: self.pos.target_capacity(),
+        }
+    }
+
+    // Grow or shrink the buffer's backing storage without tearing down the
+    // writer/reader pair, following the same target-vs-actual-capacity
+    // split as TCP send/receive buffers: `new_capacity` is a target that
+    // gets rounded up to the next power of two and is only honored if it
+    // doesn't discard data no reader has consumed yet. `Writer` isn't
+    // `Clone`, so this can never race with another call on the same
+    // writer - but readers calling `as_slice`/`read`/`get_from` on another
+    // thread while this runs are fine: `shm_snapshot` is what keeps them
+    // consistent across the swap. Absolute indices
+    // (`produced_index`/`latest_index`/`get_from`) keep meaning the same
+    // sample across a resize; only the physical offset they map to
+    // changes. The old allocation is retired rather than freed, so slices
+    // already handed out from it stay valid.
+    #[cfg(feature = "std")]
+    pub fn set_target_capacity(&mut self, new_capacity: usize) -> Result<(), ResizeError> {
+        let new_capacity = new_capacity.next_power_of_two();
+        let (old_ptr, capacity, origin) = shm_snapshot(&self.shm, &self.pos);
+        let occupied = capacity - self.pos.write_len(capacity);
+        if new_capacity < occupied {
+            return Err(ResizeError::WouldDiscardData);
+        }
+
+        let new_shm = SharedMemory::<T>::new(new_capacity).map_err(ResizeError::Allocate)?;
+        let min_read = self.pos.min_read();
+        let old_offset = min_read.wrapping_sub(origin) & (capacity - 1);
+        if occupied > 0 {
+            unsafe {
+                ptr::copy_nonoverlapping(old_ptr.offset(old_offset as isize), new_shm.ptr, occupied);
+            }
+        }
+        let new_generation = Arc::new(Generation {
+            shm: Arc::new(new_shm),
+            capacity: new_capacity,
+            origin: min_read,
+        });
+        let old_generation = core::mem::replace(
+            &mut *self.shm.current.write().unwrap(), new_generation);
+        // Keep the old mapping alive rather than dropping it here: any
+        // slice a reader already took from it may still be in use.
+        self.shm.retired.lock().unwrap().push(old_generation.shm.clone());
+        self.pos.target_capacity.store(new_capacity, Ordering::Release);
+        Ok(())
     }
 }
 
 
 #[derive(Debug)]
-pub struct Reader<T> {
-    shm: Arc<SharedMemory<T>>,
-    pos: Arc<Mutex<Position>>,
-    cond: Arc<Condvar>
+pub struct Reader<T, B = DefaultBlocking> {
+    shm: ShmHandle<T>,
+    pos: Arc<Position>,
+    read: Arc<AtomicUsize>,
+    blocking: Arc<B>,
 }
 
-impl<T: Copy> Reader<T> {
+impl<T: Copy, B: Blocking> Reader<T, B> {
     pub fn as_slice(&self) -> &[T] {
-        let pos = self.pos.lock().unwrap();
-        let (read_offset, read_len) = pos.read_offset_len();
+        let (ptr, capacity, origin) = shm_snapshot(&self.shm, &self.pos);
+        let (read_offset, read_len) = self.pos.read_offset_len(&self.read, capacity, origin);
         // len is number of item
         unsafe {
-            slice::from_raw_parts(
-                self.shm.ptr.offset(read_offset as isize),
-                read_len)
+            slice::from_raw_parts(ptr.offset(read_offset as isize), read_len)
         }
     }
 
     pub fn consume(&mut self, amount: usize) {
-        let mut pos = self.pos.lock().unwrap();
-        pos.consume(amount);
-        // Notify waiting writer
-        self.cond.notify_one();
+        self.pos.consume(&self.read, amount);
+        // Notify a writer parked waiting for space, if this strategy has one.
+        self.blocking.notify();
     }
 
-    pub fn read(&mut self, buf: &mut [T]) -> Result<usize, io::Error> {
+    pub fn read(&mut self, buf: &mut [T]) -> usize {
+        let (ptr, capacity, origin) = shm_snapshot(&self.shm, &self.pos);
         let copy_len = {
-            let mut pos = self.pos.lock().unwrap();
-            let (read_offset, read_len) = pos.read_offset_len();
+            let (read_offset, read_len) = self.pos.read_offset_len(&self.read, capacity, origin);
             let copy_len = read_len.min(buf.len());
             // len is number of item
             let src = unsafe {
-                slice::from_raw_parts(
-                    self.shm.ptr.offset(read_offset as isize),
-                    read_len)
+                slice::from_raw_parts(ptr.offset(read_offset as isize), read_len)
             };
             buf[0..copy_len].copy_from_slice(&src[0..copy_len]);
             copy_len
         };
         self.consume(copy_len);
-        Ok(copy_len)
+        copy_len
     }
 
-    pub fn read_exact(&mut self, buf: &mut [T]) -> Result<usize, io::Error> {
-        {
-            let mut pos = self.pos.lock().unwrap();
-            let mut read_len = pos.read_len();
-            // Block and wait for enough bytes to read
-            while read_len < buf.len() {
-                pos = self.cond.wait(pos).unwrap();
-                read_len = pos.read_len();
-            }
+    pub fn read_exact(&mut self, buf: &mut [T]) -> usize {
+        // Fast path: the data is probably already there, so don't even
+        // touch the blocking strategy.
+        if self.pos.read_len(&self.read) < buf.len() {
+            let pos = &self.pos;
+            let read = &self.read;
+            self.blocking.wait_while(&mut || pos.read_len(read) < buf.len());
         }
         self.read(buf)
     }
+
+    // Absolute index of the next sample that will be produced, i.e. the
+    // most recent position this reader can currently see.
+    pub fn latest_index(&self) -> usize {
+        self.pos.produced()
+    }
+
+    // Slice of `len` samples starting at the absolute index `abs_index`,
+    // still resident in the buffer, regardless of this reader's own read
+    // cursor. Returns `None` if `abs_index` has already been overwritten
+    // or hasn't been produced yet. Because the double-mapping guarantees
+    // the window is contiguous in virtual memory, any still-resident
+    // range can be returned as a single slice without copying.
+    pub fn get_from(&self, abs_index: usize, len: usize) -> Option<&[T]> {
+        let (ptr, capacity, origin) = shm_snapshot(&self.shm, &self.pos);
+        if len > capacity {
+            return None;
+        }
+        let produced = self.pos.produced();
+        if abs_index < self.pos.oldest_retained(capacity, origin) || abs_index.wrapping_add(len) > produced {
+            return None;
+        }
+        let offset = abs_index.wrapping_sub(origin) & (capacity - 1);
+        Some(unsafe {
+            slice::from_raw_parts(ptr.offset(offset as isize), len)
+        })
+    }
+
+    pub fn limits(&self) -> BufferLimits {
+        let (_, capacity, _) = shm_snapshot(&self.shm, &self.pos);
+        BufferLimits {
+            len: self.pos.read_len(&self.read),
+            capacity,
+            target_capacity: self.pos.target_capacity(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<B: Blocking> io::Write for Writer<u8, B> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let limits = self.limits();
+        if !buf.is_empty() && limits.len == limits.capacity {
+            return Err(io::Error::from(io::ErrorKind::WouldBlock));
+        }
+        Ok(Writer::write(self, buf))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<B: Blocking> io::Read for Reader<u8, B> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        // `Ok(0)` means EOF per the `Read` contract; the ring is merely
+        // empty, not closed, so that has to be `WouldBlock` instead, same
+        // as the `Write` side does when full.
+        if !buf.is_empty() && self.limits().len == 0 {
+            return Err(io::Error::from(io::ErrorKind::WouldBlock));
+        }
+        Ok(Reader::read(self, buf))
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl<B: Blocking> bytes::Buf for Reader<u8, B> {
+    fn remaining(&self) -> usize {
+        self.pos.read_len(&self.read)
+    }
+
+    fn chunk(&self) -> &[u8] {
+        // The double-mapping keeps the readable region contiguous, so the
+        // whole available span is always a single chunk.
+        self.as_slice()
+    }
+
+    fn advance(&mut self, cnt: usize) {
+        self.consume(cnt);
+    }
+}
+
+#[cfg(feature = "bytes")]
+unsafe impl<B: Blocking> bytes::BufMut for Writer<u8, B> {
+    fn remaining_mut(&self) -> usize {
+        let limits = self.limits();
+        limits.capacity - limits.len
+    }
+
+    unsafe fn advance_mut(&mut self, cnt: usize) {
+        self.produce(cnt);
+    }
+
+    fn chunk_mut(&mut self) -> &mut bytes::buf::UninitSlice {
+        // Likewise, the writable region is always contiguous.
+        bytes::buf::UninitSlice::new(self.as_mut_slice())
+    }
+}
+
+#[cfg(feature = "std")]
+pub fn new<T>(capacity: usize) -> (Writer<T>, Reader<T>) {
+    new_with_blocking(capacity, DefaultBlocking::default())
 }
 
+#[cfg(not(feature = "std"))]
 pub fn new<T>(capacity: usize) -> (Writer<T>, Reader<T>) {
+    new_with_blocking(capacity, DefaultBlocking::default())
+}
+
+// Like `new`, but with a caller-supplied `Blocking` strategy instead of the
+// default condvar (std) / spin (no_std) one - e.g. an async-waker-based or
+// hybrid spin/park strategy.
+#[cfg(feature = "std")]
+pub fn new_with_blocking<T, B: Blocking>(capacity: usize, blocking: B) -> (Writer<T, B>, Reader<T, B>) {
+    let (writer, mut readers) = new_multi_with_blocking(capacity, 1, blocking);
+    (writer, readers.remove(0))
+}
+
+#[cfg(not(feature = "std"))]
+pub fn new_with_blocking<T, B: Blocking>(capacity: usize, blocking: B) -> (Writer<T, B>, Reader<T, B>) {
     let pow_two_cap = capacity.next_power_of_two();
 
-    let shm = Arc::new(SharedMemory::<T>::new(pow_two_cap).unwrap());
-    let pos = Arc::new(Mutex::new(Position::new(pow_two_cap)));
-    let cond = Arc::new(Condvar::new());
+    let shm: ShmHandle<T> = Arc::new(SharedMemory::<T>::new(pow_two_cap).unwrap());
+    let pos = Arc::new(Position::new(pow_two_cap, 1));
+    let blocking = Arc::new(blocking);
 
     let writer = Writer{
         shm: shm.clone(),
         pos: pos.clone(),
-        cond: cond.clone(),
+        blocking: blocking.clone(),
     };
+    let read = pos.reader_cursors().remove(0);
+    let reader = Reader{shm, pos, read, blocking};
+
+    (writer, reader)
+}
+
+// One writer feeding `n_readers` independent readers, each consuming the
+// same stream at its own pace (GNU Radio style one-to-many buffer). The
+// writer only reclaims space once every reader has moved past it.
+// Requires `std`: the dynamic reader registry needs a `Mutex`.
+#[cfg(feature = "std")]
+pub fn new_multi<T>(capacity: usize, n_readers: usize) -> (Writer<T>, Vec<Reader<T>>) {
+    new_multi_with_blocking(capacity, n_readers, DefaultBlocking::default())
+}
+
+// Like `new_multi`, but with a caller-supplied `Blocking` strategy instead
+// of the default condvar one.
+#[cfg(feature = "std")]
+pub fn new_multi_with_blocking<T, B: Blocking>(capacity: usize, n_readers: usize, blocking: B) -> (Writer<T, B>, Vec<Reader<T, B>>) {
+    let pow_two_cap = capacity.next_power_of_two();
+
+    let shm: ShmHandle<T> = Arc::new(ShmCell {
+        current: RwLock::new(Arc::new(Generation {
+            shm: Arc::new(SharedMemory::<T>::new(pow_two_cap).unwrap()),
+            capacity: pow_two_cap,
+            origin: 0,
+        })),
+        retired: Mutex::new(Vec::new()),
+    });
+    let pos = Arc::new(Position::new(pow_two_cap, n_readers));
+    let blocking = Arc::new(blocking);
 
-    let reader = Reader{
+    let writer = Writer{
         shm: shm.clone(),
         pos: pos.clone(),
-        cond: cond.clone(),
+        blocking: blocking.clone(),
     };
 
-    (writer, reader)
+    let readers = pos.reader_cursors().into_iter().map(|read| Reader{
+        shm: shm.clone(),
+        pos: pos.clone(),
+        read,
+        blocking: blocking.clone(),
+    }).collect();
+
+    (writer, readers)
 }
 
 // Tests
 #[cfg(test)]
 mod tests {
 
-    use crate::vmcircbuffer::{new};
+    // Most tests below exercise std-only behavior (fan-out, io traits,
+    // resizing) or use std-only macros (println!/vec!) directly.
+    #[cfg(feature = "std")]
+    use crate::vmcircbuffer::new;
+    #[cfg(feature = "std")]
+    use crate::vmcircbuffer::new_multi;
+    // `no_std_produce_consume_roundtrip` below is the one no_std caller.
+    #[cfg(not(feature = "std"))]
+    use crate::vmcircbuffer::new;
 
     #[test]
+    // Uses println!/vec! directly, which aren't available without std.
+    #[cfg(feature = "std")]
     fn create_buffer() {
 
         let (mut w, mut r) = new::<f32>(1024);
-        let cap = w.pos.lock().unwrap().capacity;
+        let cap = w.limits().capacity;
         println!("{}", cap);
         w.produce(30);
         //r.consume(30);
 
         let v = vec![100_f32; 100];
-        let mut out = vec![0_f32; 200];
+        // 30 produced directly above plus the 100 written below: reading
+        // more than that would block forever waiting for data that's
+        // never produced.
+        let mut out = vec![0_f32; 130];
 
         w.write(v.as_slice());
         r.read_exact(out.as_mut_slice());
@@ -215,4 +752,202 @@ mod tests {
 
         println!("{:?}", out);
     }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn fan_out_to_independent_readers() {
+        // Small capacity, filled completely, so write_len()'s reclaim
+        // gating is actually exercised by the asserts below.
+        let (mut w, mut readers) = new_multi::<f32>(16, 2);
+        let mut slow = readers.remove(1);
+        let mut fast = readers.remove(0);
+
+        let v = vec![1_f32; 16];
+        w.write(v.as_slice());
+        assert_eq!(w.limits().len, 16);
+
+        let mut out = vec![0_f32; 16];
+        fast.read_exact(out.as_mut_slice());
+
+        // the slow reader hasn't consumed yet, so the writer can't reclaim
+        // the space the fast reader already freed up on its own cursor
+        assert_eq!(w.limits().len, 16);
+
+        slow.read_exact(out.as_mut_slice());
+        assert_eq!(w.limits().len, 0);
+
+        let late = w.add_reader();
+        assert_eq!(late.limits().len, 0);
+    }
+
+    #[test]
+    // Uses Vec<f32>/vec! directly, which aren't available without std.
+    #[cfg(feature = "std")]
+    fn random_access_by_absolute_index() {
+        let (mut w, mut r) = new::<f32>(16);
+
+        let v: Vec<f32> = (0..10).map(|i| i as f32).collect();
+        w.write(v.as_slice());
+
+        // not produced yet
+        assert!(r.get_from(10, 1).is_none());
+        assert_eq!(r.latest_index(), 10);
+        assert_eq!(w.produced_index(), 10);
+
+        let s = r.get_from(2, 3).unwrap();
+        assert_eq!(s, &[2_f32, 3_f32, 4_f32]);
+
+        // free up room and write past a capacity's worth so the early
+        // samples get physically overwritten
+        let mut out = vec![0_f32; 5];
+        r.read_exact(out.as_mut_slice());
+        let filler: Vec<f32> = (10..21).map(|i| i as f32).collect();
+        w.write(filler.as_slice());
+
+        assert!(r.get_from(2, 3).is_none());
+        let s = r.get_from(5, 3).unwrap();
+        assert_eq!(s, &[5_f32, 6_f32, 7_f32]);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn io_read_write_for_byte_buffers() {
+        use std::io::{Read, Write};
+
+        let (mut w, mut r) = new::<u8>(16);
+
+        let n = (&mut w as &mut dyn Write).write(b"hello").unwrap();
+        assert_eq!(n, 5);
+
+        let mut out = [0_u8; 5];
+        (&mut r as &mut dyn Read).read_exact(&mut out).unwrap();
+        assert_eq!(&out, b"hello");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn io_read_on_empty_buffer_would_block_not_eof() {
+        // `Ok(0)` means EOF to generic `Read` consumers (read_to_end,
+        // io::copy, BufReader); an empty-but-open ring must not be
+        // reported that way, or they'd stop reading permanently even
+        // though the writer can still produce more later.
+        use std::io::{ErrorKind, Read};
+
+        let (_w, mut r) = new::<u8>(16);
+
+        let mut out = [0_u8; 4];
+        let err = (&mut r as &mut dyn Read).read(&mut out).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::WouldBlock);
+    }
+
+    #[test]
+    #[cfg(feature = "bytes")]
+    fn bytes_buf_and_buf_mut_for_byte_buffers() {
+        use bytes::{Buf, BufMut};
+
+        let (mut w, mut r) = new::<u8>(16);
+
+        w.put_slice(b"hello");
+        assert_eq!(w.remaining_mut(), 11);
+
+        assert_eq!(r.remaining(), 5);
+        assert_eq!(r.chunk(), b"hello");
+        r.advance(5);
+        assert_eq!(r.remaining(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn resize_grows_without_losing_unread_data() {
+        let (mut w, mut r) = new::<f32>(16);
+
+        let v: Vec<f32> = (0..10).map(|i| i as f32).collect();
+        w.write(v.as_slice());
+
+        w.set_target_capacity(64).unwrap();
+
+        let limits = w.limits();
+        assert_eq!(limits.capacity, 64);
+        assert_eq!(limits.target_capacity, 64);
+        assert_eq!(limits.len, 10);
+
+        let mut out = vec![0_f32; 10];
+        r.read_exact(out.as_mut_slice());
+        assert_eq!(out, v);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn resize_rejects_shrink_that_would_drop_data() {
+        let (mut w, mut r) = new::<f32>(16);
+
+        let v = vec![1_f32; 10];
+        w.write(v.as_slice());
+
+        assert!(matches!(w.set_target_capacity(4), Err(super::ResizeError::WouldDiscardData)));
+
+        let mut out = vec![0_f32; 10];
+        r.read_exact(out.as_mut_slice());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn resize_preserves_absolute_indices() {
+        // abs_index values handed out before a resize (via produced_index /
+        // latest_index) must still resolve correctly with get_from after
+        // the resize, even though the data has physically moved to a new
+        // allocation at a new capacity.
+        let (mut w, r) = new::<f32>(16);
+
+        let v: Vec<f32> = (0..10).map(|i| i as f32).collect();
+        w.write(v.as_slice());
+        let before_resize = w.produced_index();
+
+        w.set_target_capacity(64).unwrap();
+
+        assert_eq!(r.get_from(before_resize - 3, 3).unwrap(), &[7_f32, 8_f32, 9_f32]);
+
+        let more: Vec<f32> = (10..20).map(|i| i as f32).collect();
+        w.write(more.as_slice());
+        assert_eq!(r.get_from(before_resize, 3).unwrap(), &[10_f32, 11_f32, 12_f32]);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn resize_between_partial_reads_keeps_read_cursor_consistent() {
+        // Single-threaded: a reader that consumed part of the data before a
+        // resize must read the rest from the new allocation, with its
+        // offset computed from the new generation's (capacity, origin)
+        // rather than the old one's.
+        let (mut w, mut r) = new::<f32>(16);
+
+        let v: Vec<f32> = (0..10).map(|i| i as f32).collect();
+        w.write(v.as_slice());
+
+        let mut out = vec![0_f32; 5];
+        r.read_exact(out.as_mut_slice());
+        assert_eq!(out, &[0_f32, 1_f32, 2_f32, 3_f32, 4_f32]);
+
+        w.set_target_capacity(64).unwrap();
+
+        let mut rest = vec![0_f32; 5];
+        r.read_exact(rest.as_mut_slice());
+        assert_eq!(rest, &[5_f32, 6_f32, 7_f32, 8_f32, 9_f32]);
+    }
+
+    #[test]
+    #[cfg(not(feature = "std"))]
+    fn no_std_produce_consume_roundtrip() {
+        let (mut w, mut r) = new::<u8>(16);
+
+        let slot = w.as_mut_slice();
+        slot[0] = 42;
+        w.produce(1);
+
+        assert_eq!(r.limits().len, 1);
+        assert_eq!(r.as_slice()[0], 42);
+
+        r.consume(1);
+        assert_eq!(r.limits().len, 0);
+    }
 }